@@ -14,6 +14,26 @@
 //! This plugin lets you use its colors to modify other `Form`s with
 //! the `Catppuccin::modify` function. It also has a `no_background`
 //! function, if you don't want the background to change.
+//!
+//! `Catppuccin::styles` lets you configure per-scope style modifiers
+//! (italic/bold/underline) for comments, keywords, strings, and the
+//! like, instead of using the fixed set baked into each colorscheme.
+//!
+//! `Catppuccin::dim_inactive` dims the background of inactive
+//! regions, blending `base` toward `crust` by a given percentage.
+//!
+//! `Catppuccin::auto` replaces the four static colorschemes above
+//! with a single `catppuccin` colorscheme that switches between a
+//! light and dark flavour as the signal set through
+//! `set_dark_mode` changes.
+//!
+//! `Catppuccin::term_colors` maps the palette onto the 16 standard
+//! terminal colors, forwarded to any callback registered through
+//! `on_term_colors`.
+//!
+//! `Catppuccin::custom` registers an additional colorscheme built
+//! from a caller-supplied `Colors` palette, for derivative or
+//! per-project flavours.
 use std::marker::PhantomData;
 
 use duat_core::form::{self, Form, add_colorscheme};
@@ -21,6 +41,11 @@ use duat_core::form::{self, Form, add_colorscheme};
 pub struct Catppuccin<U> {
     no_background: bool,
     modifications: Box<dyn Fn(Colors) + Send + Sync + 'static>,
+    styles: Option<Styles>,
+    dim_inactive: Option<u8>,
+    auto: Option<(Flavour, Flavour)>,
+    term_colors: bool,
+    customs: Vec<(&'static str, Colors)>,
     _u: PhantomData<U>,
 }
 
@@ -29,6 +54,11 @@ impl<U: duat_core::ui::Ui> duat_core::Plugin<U> for Catppuccin<U> {
         Self {
             no_background: false,
             modifications: Box::new(|_| {}),
+            styles: None,
+            dim_inactive: None,
+            auto: None,
+            term_colors: false,
+            customs: Vec::new(),
             _u: PhantomData,
         }
     }
@@ -36,14 +66,72 @@ impl<U: duat_core::ui::Ui> duat_core::Plugin<U> for Catppuccin<U> {
     /// Adds the catppuccin colorschemes
     ///
     /// This will add the Latte, Frappe, Macchiato, and Mocha flavors,
-    /// modified by the options passed to [`Catppuccin`]
+    /// modified by the options passed to [`Catppuccin`]. If
+    /// [`Catppuccin::auto`] was called, a single `catppuccin`
+    /// colorscheme is added instead, switching between the chosen
+    /// flavours as [`set_dark_mode`] is called. Any palettes passed
+    /// to [`Catppuccin::custom`] are added alongside these.
     fn plug(self) {
         let no_bg = self.no_background;
+        let styles = self.styles;
+        let dim_inactive = self.dim_inactive;
+        let term_colors = self.term_colors;
         let m = Box::leak(self.modifications);
-        add_colorscheme(ColorScheme::latte(m).no_bg(no_bg));
-        add_colorscheme(ColorScheme::frappe(m).no_bg(no_bg));
-        add_colorscheme(ColorScheme::macchiato(m).no_bg(no_bg));
-        add_colorscheme(ColorScheme::mocha(m).no_bg(no_bg));
+
+        if let Some((light, dark)) = self.auto {
+            let auto = AutoColorScheme {
+                light,
+                dark,
+                no_background: no_bg,
+                modifications: m,
+                styles,
+                dim_inactive,
+                term_colors,
+            };
+            let _ = AUTO_CONFIG.set(auto);
+            add_colorscheme(auto);
+        } else {
+            add_colorscheme(
+                ColorScheme::latte(m)
+                    .no_bg(no_bg)
+                    .styles(styles)
+                    .dim_inactive(dim_inactive)
+                    .term_colors(term_colors),
+            );
+            add_colorscheme(
+                ColorScheme::frappe(m)
+                    .no_bg(no_bg)
+                    .styles(styles)
+                    .dim_inactive(dim_inactive)
+                    .term_colors(term_colors),
+            );
+            add_colorscheme(
+                ColorScheme::macchiato(m)
+                    .no_bg(no_bg)
+                    .styles(styles)
+                    .dim_inactive(dim_inactive)
+                    .term_colors(term_colors),
+            );
+            add_colorscheme(
+                ColorScheme::mocha(m)
+                    .no_bg(no_bg)
+                    .styles(styles)
+                    .dim_inactive(dim_inactive)
+                    .term_colors(term_colors),
+            );
+        }
+
+        for (name, colors) in self.customs {
+            add_colorscheme(CustomColorScheme {
+                name,
+                colors,
+                no_background: no_bg,
+                modifications: m,
+                styles,
+                dim_inactive,
+                term_colors,
+            });
+        }
     }
 }
 
@@ -56,6 +144,68 @@ impl<U> Catppuccin<U> {
         Self { no_background: true, ..self }
     }
 
+    /// Configures per-scope style modifiers (italic/bold/underline)
+    ///
+    /// Each field in [`Styles`] layers its [`Style`] flags onto the
+    /// corresponding tree-sitter [`Form`] before `modify` runs,
+    /// letting you tune emphasis without rewriting every form:
+    ///
+    /// ```rust
+    /// # use duat_catppuccin as catppuccin;
+    /// # fn plug(plug: Catppuccin) {}
+    /// use catppuccin::{Catppuccin, Style, Styles};
+    ///
+    /// plug(Catppuccin::new().styles(Styles {
+    ///     comments: Style::Italic,
+    ///     keywords: Style::None,
+    ///     ..Styles::default()
+    /// }));
+    /// ```
+    pub fn styles(self, styles: Styles) -> Self {
+        Self { styles: Some(styles), ..self }
+    }
+
+    /// Dims the background of inactive regions
+    ///
+    /// The `Inactive` form's background is set to `base` blended
+    /// toward `crust` by `percentage` (0-100), giving unfocused
+    /// splits a visual focus cue.
+    pub fn dim_inactive(self, percentage: u8) -> Self {
+        Self { dim_inactive: Some(percentage), ..self }
+    }
+
+    /// Automatically switches between a light and dark [`Flavour`]
+    ///
+    /// Instead of the four static flavours, a single `catppuccin`
+    /// colorscheme is added, applying `light` or `dark` depending on
+    /// the current signal set through [`set_dark_mode`]. This lets
+    /// you follow the OS light/dark preference live, instead of
+    /// requiring the user to manually re-invoke `colorscheme`.
+    pub fn auto(self, light: Flavour, dark: Flavour) -> Self {
+        Self { auto: Some((light, dark)), ..self }
+    }
+
+    /// Maps the palette onto the 16 standard terminal colors
+    ///
+    /// This lets embedded terminals match the editor's theme, once a
+    /// callback is registered through [`on_term_colors`] to receive
+    /// the 16 computed colors.
+    pub fn term_colors(self) -> Self {
+        Self { term_colors: true, ..self }
+    }
+
+    /// Registers an additional colorscheme built from a custom palette
+    ///
+    /// This lets you ship a derivative palette or a per-project
+    /// tweak without forking the crate. The new colorscheme honors
+    /// [`Catppuccin::no_background`], [`Catppuccin::styles`], and the
+    /// other options, the same way the four built-in flavours do.
+    pub fn custom(self, name: &'static str, colors: Colors) -> Self {
+        let mut customs = self.customs;
+        customs.push((name, colors));
+        Self { customs, ..self }
+    }
+
     /// Lets you modify forms, based on the chosen colorscheme
     ///
     /// For example, if you want red delimiters, you can do this:
@@ -67,7 +217,7 @@ impl<U> Catppuccin<U> {
     /// use catppuccin::Catppuccin;
     ///
     /// plug(Catppuccin::new().modify(|colors| {
-    ///     form::set("punctuation.delimiter", colors.red);
+    ///     form::set("punctuation.delimiter", colors.red.hex());
     /// }));
     /// ```
     pub fn modify<R>(self, modifications: impl Fn(Colors) -> R + Send + Sync + 'static) -> Self {
@@ -78,8 +228,12 @@ impl<U> Catppuccin<U> {
     }
 }
 
-#[derive(Default)]
-enum Flavour {
+/// Which Catppuccin flavour a [`Colors`] palette belongs to
+///
+/// Lets plugin authors match on a flavour or fetch its palette
+/// without going through [`Catppuccin`] itself.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Flavour {
     Latte,
     Frappe,
     Macchiato,
@@ -87,140 +241,264 @@ enum Flavour {
     Mocha,
 }
 
+impl Flavour {
+    /// Returns the [`Colors`] palette for this flavour
+    pub fn palette(self) -> Colors {
+        match self {
+            Flavour::Latte => LATTE,
+            Flavour::Frappe => FRAPPE,
+            Flavour::Macchiato => MACCHIATO,
+            Flavour::Mocha => MOCHA,
+        }
+    }
+
+    /// Returns this flavour's name, e.g. `"mocha"`
+    pub fn name(self) -> &'static str {
+        match self {
+            Flavour::Latte => "latte",
+            Flavour::Frappe => "frappe",
+            Flavour::Macchiato => "macchiato",
+            Flavour::Mocha => "mocha",
+        }
+    }
+}
+
 struct ColorScheme {
     flavour: Flavour,
+    name: &'static str,
     no_background: bool,
     modifications: &'static (dyn Fn(Colors) + Send + Sync),
+    styles: Option<Styles>,
+    dim_inactive: Option<u8>,
+    term_colors: bool,
 }
 
 impl form::ColorScheme for ColorScheme {
     fn apply(&self) {
-        let c = match self.flavour {
-            Flavour::Latte => LATTE,
-            Flavour::Frappe => FRAPPE,
-            Flavour::Macchiato => MACCHIATO,
-            Flavour::Mocha => MOCHA,
-        };
+        apply_colors(
+            self.flavour.palette(),
+            self.no_background,
+            self.styles,
+            self.dim_inactive,
+            self.term_colors,
+            self.modifications,
+        );
+    }
 
-        if self.no_background {
-            form::set("Default", Form::with(c.text));
-        } else {
-            form::set("Default", Form::with(c.text).on(c.base));
-        }
+    fn name(&self) -> &'static str {
+        self.name
+    }
+}
+
+/// Sets all of this plugin's [`Form`]s for a given [`Colors`] palette
+///
+/// Shared by [`ColorScheme::apply`], [`AutoColorScheme::apply`], and
+/// [`CustomColorScheme::apply`], so that a flavour-driven or
+/// caller-supplied palette can reuse the same mapping.
+fn apply_colors(
+    c: Colors,
+    no_background: bool,
+    styles: Option<Styles>,
+    dim_inactive: Option<u8>,
+    term_colors: bool,
+    modifications: &(dyn Fn(Colors) + Send + Sync),
+) {
 
+    if no_background {
+        form::set("Default", Form::with(c.text.hex()));
+    } else {
+        form::set("Default", Form::with(c.text.hex()).on(c.base.hex()));
+    }
+
+    form::set_many!(
+        // Base Duat Forms
+        ("DefaultOk", Form::with(c.sapphire.hex())),
+        ("AccentOk", Form::with(c.sky.hex()).bold()),
+        ("DefaultErr", Form::with(c.maroon.hex())),
+        ("AccentErr", Form::with(c.red.hex()).bold()),
+        ("DefaultHint", Form::with(c.text.hex())),
+        ("AccentHint", Form::with(c.subtext0.hex()).bold()),
+        ("MainCursor", Form::reverse()),
+        ("ExtraCursor", Form::reverse()),
+        ("MainSelection", Form::with(c.base.hex()).on(c.overlay1.hex())),
+        ("ExtraSelection", Form::with(c.base.hex()).on(c.overlay0.hex())),
+        (
+            "Inactive",
+            if let Some(pct) = dim_inactive {
+                Form::with(c.overlay2.hex()).on(c.base.mix(c.crust, pct as f32 / 100.0).hex())
+            } else {
+                Form::with(c.overlay2.hex())
+            }
+        ),
+        // Other Duat Forms
+        ("LineNum", Form::with(c.overlay2.hex())),
+        ("MainLineNum", Form::with(c.yellow.hex())),
+        ("WrappedLineNum", Form::with(c.teal.hex())),
+        ("File", Form::with(c.yellow.hex())),
+        ("Selections", Form::with(c.blue.hex())),
+        ("Coord", Form::with(c.peach.hex())),
+        ("Separator", Form::with(c.teal.hex())),
+        ("Mode", Form::with(c.green.hex())),
+        // Tree sitter Forms
+        ("type", Form::with(c.yellow.hex()).italic()),
+        ("type.builtin", Form::with(c.yellow.hex()).reset()),
+        ("function", Form::with(c.blue.hex()).reset()),
+        ("comment", Form::with(c.overlay1.hex())),
+        ("comment.documentation", Form::with(c.overlay1.hex()).bold()),
+        ("punctuation.bracket", Form::with(c.subtext0.hex())),
+        ("punctuation.delimiter", Form::with(c.subtext0.hex())),
+        ("constant", Form::with(c.overlay1.hex())),
+        ("constant.builtin", Form::with(c.peach.hex())),
+        ("character", Form::with(c.peach.hex())),
+        ("number", Form::with(c.peach.hex())),
+        ("variable.parameter", Form::italic()),
+        ("variable.builtin", Form::with(c.peach.hex())),
+        ("variable.other.member", Form::with(c.teal.hex())),
+        ("label", Form::with(c.sapphire.hex())),
+        ("lifetime", Form::with(c.sapphire.hex())),
+        ("keyword", Form::with(c.mauve.hex())),
+        ("keyword.control.conditional", Form::with(c.mauve.hex())),
+        ("keyword.storage.modifier", Form::with(c.mauve.hex())),
+        ("string", Form::with(c.green.hex())),
+        ("string.regexp", Form::with(c.peach.hex())),
+        ("string.special", Form::with(c.peach.hex())),
+        ("escape", Form::with(c.pink.hex())),
+        ("constant.character.escape", Form::with(c.pink.hex())),
+        ("attribute", Form::with(c.mauve.hex())),
+        ("operator", Form::with(c.sapphire.hex())),
+        ("constructor", Form::with(c.peach.hex())),
+        ("function.macro", Form::with(c.mauve.hex())),
+        ("module", Form::with(c.blue.hex()).italic()),
+        ("tag", Form::with(c.blue.hex())),
+        ("punctuation.special", Form::with(c.mauve.hex())),
+        // Markup Forms
+        ("markup", Form::new()),
+        ("markup.strong", Form::with(c.maroon.hex()).bold()),
+        ("markup.italic", Form::with(c.maroon.hex()).italic()),
+        ("markup.strikethrough", Form::new().crossed_out()),
+        ("markup.underline", Form::underlined()),
+        ("markup.heading", Form::with(c.blue.hex()).bold()),
+        ("markup.math", Form::with(c.yellow.hex())),
+        ("markup.quote", Form::with(c.maroon.hex()).bold()),
+        ("markup.environment", Form::with(c.pink.hex())),
+        ("markup.environment.name", Form::with(c.blue.hex())),
+        ("markup.link", Form::with(c.lavender.hex()).underlined()),
+        ("markup.raw", Form::with(c.teal.hex())),
+        ("markup.list", Form::with(c.yellow.hex())),
+        ("markup.list.checked", Form::with(c.green.hex())),
+        ("markup.list.unchecked", Form::with(c.overlay1.hex())),
+        // Plugin and Ui Forms
+        ("VertRule", Form::with(c.subtext0.hex())),
+        ("Frame", Form::with(c.subtext0.hex()).on(c.base.hex()))
+    );
+
+    if let Some(styles) = styles {
         form::set_many!(
-            // Base Duat Forms
-            ("DefaultOk", Form::with(c.sapphire)),
-            ("AccentOk", Form::with(c.sky).bold()),
-            ("DefaultErr", Form::with(c.maroon)),
-            ("AccentErr", Form::with(c.red).bold()),
-            ("DefaultHint", Form::with(c.text)),
-            ("AccentHint", Form::with(c.subtext0).bold()),
-            ("MainCursor", Form::reverse()),
-            ("ExtraCursor", Form::reverse()),
-            ("MainSelection", Form::with(c.base).on(c.overlay1)),
-            ("ExtraSelection", Form::with(c.base).on(c.overlay0)),
-            ("Inactive", Form::with(c.overlay2)),
-            // Other Duat Forms
-            ("LineNum", Form::with(c.overlay2)),
-            ("MainLineNum", Form::with(c.yellow)),
-            ("WrappedLineNum", Form::with(c.teal)),
-            ("File", Form::with(c.yellow)),
-            ("Selections", Form::with(c.blue)),
-            ("Coord", Form::with(c.peach)),
-            ("Separator", Form::with(c.teal)),
-            ("Mode", Form::with(c.green)),
-            // Tree sitter Forms
-            ("type", Form::with(c.yellow).italic()),
-            ("type.builtin", Form::with(c.yellow).reset()),
-            ("function", Form::with(c.blue).reset()),
-            ("comment", Form::with(c.overlay1)),
-            ("comment.documentation", Form::with(c.overlay1).bold()),
-            ("punctuation.bracket", Form::with(c.subtext0)),
-            ("punctuation.delimiter", Form::with(c.subtext0)),
-            ("constant", Form::with(c.overlay1)),
-            ("constant.builtin", Form::with(c.peach)),
-            ("character", Form::with(c.peach)),
-            ("number", Form::with(c.peach)),
-            ("variable.parameter", Form::italic()),
-            ("variable.builtin", Form::with(c.peach)),
-            ("label", Form::with(c.green)),
-            ("keyword", Form::with(c.mauve)),
-            ("string", Form::with(c.green)),
-            ("escape", Form::with(c.peach)),
-            ("attribute", Form::with(c.mauve)),
-            ("operator", Form::with(c.sapphire)),
-            ("constructor", Form::with(c.peach)),
-            ("module", Form::with(c.blue).italic()),
-            // Markup Forms
-            ("markup", Form::new()),
-            ("markup.strong", Form::with(c.maroon).bold()),
-            ("markup.italic", Form::with(c.maroon).italic()),
-            ("markup.strikethrough", Form::new().crossed_out()),
-            ("markup.underline", Form::underlined()),
-            ("markup.heading", Form::with(c.blue).bold()),
-            ("markup.math", Form::with(c.yellow)),
-            ("markup.quote", Form::with(c.maroon).bold()),
-            ("markup.environment", Form::with(c.pink)),
-            ("markup.environment.name", Form::with(c.blue)),
-            ("markup.link", Form::with(c.lavender).underlined()),
-            ("markup.raw", Form::with(c.teal)),
-            ("markup.list", Form::with(c.yellow)),
-            ("markup.list.checked", Form::with(c.green)),
-            ("markup.list.unchecked", Form::with(c.overlay1)),
-            // Plugin and Ui Forms
-            ("VertRule", Form::with(c.subtext0)),
-            ("Frame", Form::with(c.subtext0).on(c.base))
+            ("comment", styled(Form::with(c.overlay1.hex()), styles.comments)),
+            ("function", styled(Form::with(c.blue.hex()).reset(), styles.functions)),
+            ("keyword", styled(Form::with(c.mauve.hex()), styles.keywords)),
+            ("string", styled(Form::with(c.green.hex()), styles.strings)),
+            ("variable", styled(Form::with(c.text.hex()), styles.variables)),
+            ("number", styled(Form::with(c.peach.hex()), styles.numbers)),
+            ("boolean", styled(Form::with(c.peach.hex()), styles.booleans)),
+            ("type", styled(Form::with(c.yellow.hex()), styles.types)),
+            ("operator", styled(Form::with(c.sapphire.hex()), styles.operators)),
+            (
+                "keyword.control.conditional",
+                styled(Form::with(c.mauve.hex()), styles.conditionals)
+            ),
+            ("keyword.repeat", styled(Form::with(c.mauve.hex()), styles.loops))
         );
-
-        (self.modifications)(c)
     }
 
-    fn name(&self) -> &'static str {
-        match self.flavour {
-            Flavour::Latte => "catppuccin-latte",
-            Flavour::Frappe => "catppuccin-frappe",
-            Flavour::Macchiato => "catppuccin-macchiato",
-            Flavour::Mocha => "catppuccin-mocha",
+    if term_colors {
+        if let Some(hook) = TERM_COLORS_HOOK.get() {
+            hook(term_colors_for(c));
         }
     }
+
+    modifications(c)
+}
+
+/// Maps a [`Colors`] palette onto the 16 standard terminal color
+/// slots, following Catppuccin's conventional assignments (e.g.
+/// `red` to the red slot, `peach` to bright red, `surface1`/
+/// `surface2` to black/bright black, `subtext1`/`text` to
+/// white/bright white)
+fn term_colors_for(c: Colors) -> [(u8, u8, u8); 16] {
+    [
+        c.surface1.rgb(),
+        c.red.rgb(),
+        c.green.rgb(),
+        c.yellow.rgb(),
+        c.blue.rgb(),
+        c.pink.rgb(),
+        c.teal.rgb(),
+        c.subtext1.rgb(),
+        c.surface2.rgb(),
+        c.peach.rgb(),
+        c.green.rgb(),
+        c.yellow.rgb(),
+        c.blue.rgb(),
+        c.pink.rgb(),
+        c.teal.rgb(),
+        c.text.rgb(),
+    ]
+}
+
+/// The callback registered through [`on_term_colors`], if any
+static TERM_COLORS_HOOK: std::sync::OnceLock<Box<dyn Fn([(u8, u8, u8); 16]) + Send + Sync>> =
+    std::sync::OnceLock::new();
+
+/// Registers a callback to receive the 16 standard terminal colors
+/// whenever a flavour applies
+///
+/// Duat has no terminal color hooks of its own yet, so
+/// [`Catppuccin::term_colors`] only has an effect once you've
+/// registered a callback here to forward the colors to wherever your
+/// embedding terminal reads its palette from.
+pub fn on_term_colors(f: impl Fn([(u8, u8, u8); 16]) + Send + Sync + 'static) {
+    let _ = TERM_COLORS_HOOK.set(Box::new(f));
 }
 
 impl ColorScheme {
     /// Returns the Catppuccin [`ColorScheme`] in the Latte flavour
     fn latte(modifications: &'static (dyn Fn(Colors) + Send + Sync)) -> Self {
-        Self {
-            flavour: Flavour::Latte,
-            no_background: false,
-            modifications,
-        }
+        Self::for_flavour(Flavour::Latte, modifications)
     }
 
     /// Returns the Catppuccin [`ColorScheme`] in the Frappe flavour
     fn frappe(modifications: &'static (dyn Fn(Colors) + Send + Sync)) -> Self {
-        Self {
-            flavour: Flavour::Frappe,
-            no_background: false,
-            modifications,
-        }
+        Self::for_flavour(Flavour::Frappe, modifications)
     }
 
     /// Returns the Catppuccin [`ColorScheme`] in the Macchiato
     /// flavour
     fn macchiato(modifications: &'static (dyn Fn(Colors) + Send + Sync)) -> Self {
-        Self {
-            flavour: Flavour::Macchiato,
-            no_background: false,
-            modifications,
-        }
+        Self::for_flavour(Flavour::Macchiato, modifications)
     }
 
     /// Returns the Catppuccin [`ColorScheme`] in the Mocha flavour
     fn mocha(modifications: &'static (dyn Fn(Colors) + Send + Sync)) -> Self {
+        Self::for_flavour(Flavour::Mocha, modifications)
+    }
+
+    /// Builds a [`ColorScheme`] for `flavour`, deriving its
+    /// registered name from [`Flavour::name`] so the two never drift
+    /// apart
+    fn for_flavour(
+        flavour: Flavour,
+        modifications: &'static (dyn Fn(Colors) + Send + Sync),
+    ) -> Self {
+        let name = Box::leak(format!("catppuccin-{}", flavour.name()).into_boxed_str());
         Self {
-            flavour: Flavour::Mocha,
+            flavour,
+            name,
             no_background: false,
             modifications,
+            styles: None,
+            dim_inactive: None,
+            term_colors: false,
         }
     }
 
@@ -231,148 +509,425 @@ impl ColorScheme {
     fn no_bg(self, bool: bool) -> Self {
         Self { no_background: bool, ..self }
     }
+
+    /// Sets the per-scope style modifiers, if any were configured
+    fn styles(self, styles: Option<Styles>) -> Self {
+        Self { styles, ..self }
+    }
+
+    /// Sets the inactive-region dimming percentage, if configured
+    fn dim_inactive(self, dim_inactive: Option<u8>) -> Self {
+        Self { dim_inactive, ..self }
+    }
+
+    /// Sets whether the 16 standard terminal colors should be set
+    fn term_colors(self, term_colors: bool) -> Self {
+        Self { term_colors, ..self }
+    }
 }
 
+/// A [`ColorScheme`](form::ColorScheme) that switches between a light
+/// and dark [`Flavour`] based on [`is_dark_mode`]
+///
+/// Registered by [`Catppuccin::auto`] instead of the four static
+/// flavours.
+#[derive(Clone, Copy)]
+struct AutoColorScheme {
+    light: Flavour,
+    dark: Flavour,
+    no_background: bool,
+    modifications: &'static (dyn Fn(Colors) + Send + Sync),
+    styles: Option<Styles>,
+    dim_inactive: Option<u8>,
+    term_colors: bool,
+}
+
+impl form::ColorScheme for AutoColorScheme {
+    fn apply(&self) {
+        let flavour = if is_dark_mode() { self.dark } else { self.light };
+        apply_colors(
+            flavour.palette(),
+            self.no_background,
+            self.styles,
+            self.dim_inactive,
+            self.term_colors,
+            self.modifications,
+        );
+    }
+
+    fn name(&self) -> &'static str {
+        "catppuccin"
+    }
+}
+
+/// A [`ColorScheme`](form::ColorScheme) built from a caller-supplied
+/// [`Colors`] palette
+///
+/// Registered by [`Catppuccin::custom`], letting users ship a derived
+/// or per-project palette without forking the crate.
+struct CustomColorScheme {
+    name: &'static str,
+    colors: Colors,
+    no_background: bool,
+    modifications: &'static (dyn Fn(Colors) + Send + Sync),
+    styles: Option<Styles>,
+    dim_inactive: Option<u8>,
+    term_colors: bool,
+}
+
+impl form::ColorScheme for CustomColorScheme {
+    fn apply(&self) {
+        apply_colors(
+            self.colors,
+            self.no_background,
+            self.styles,
+            self.dim_inactive,
+            self.term_colors,
+            self.modifications,
+        );
+    }
+
+    fn name(&self) -> &'static str {
+        self.name
+    }
+}
+
+static AUTO_IS_DARK: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(true);
+
+/// The configuration an [`AutoColorScheme`] was registered with,
+/// stashed away so [`set_dark_mode`] can re-apply it directly instead
+/// of depending on a host-side "re-run the active colorscheme" hook
+static AUTO_CONFIG: std::sync::OnceLock<AutoColorScheme> = std::sync::OnceLock::new();
+
+/// Returns whether a [`Catppuccin::auto`] colorscheme currently
+/// prefers its dark [`Flavour`]
+pub fn is_dark_mode() -> bool {
+    AUTO_IS_DARK.load(std::sync::atomic::Ordering::Relaxed)
+}
+
+/// Sets the light/dark signal used by a [`Catppuccin::auto`] colorscheme
+///
+/// Call this from your OS theme-change hook (or anywhere else you
+/// detect a light/dark switch) to re-apply the matching flavour's
+/// forms immediately.
+pub fn set_dark_mode(is_dark: bool) {
+    AUTO_IS_DARK.store(is_dark, std::sync::atomic::Ordering::Relaxed);
+    if let Some(config) = AUTO_CONFIG.get() {
+        form::ColorScheme::apply(config);
+    }
+}
+
+/// A style modifier that can be applied to a tree-sitter capture
+///
+/// These combine with `|`, e.g. `Style::Italic | Style::Bold`, and
+/// are used in [`Styles`] to configure [`Catppuccin::styles`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Style(u8);
+
+#[allow(non_upper_case_globals)]
+impl Style {
+    pub const None: Style = Style(0);
+    pub const Italic: Style = Style(1 << 0);
+    pub const Bold: Style = Style(1 << 1);
+    pub const Underline: Style = Style(1 << 2);
+
+    fn has(self, flag: Style) -> bool {
+        self.0 & flag.0 != 0
+    }
+}
+
+impl std::ops::BitOr for Style {
+    type Output = Style;
+
+    fn bitor(self, rhs: Style) -> Style {
+        Style(self.0 | rhs.0)
+    }
+}
+
+/// Per-scope style modifiers (italic/bold/underline)
+///
+/// Passed to [`Catppuccin::styles`] to tune emphasis on particular
+/// tree-sitter captures without rewriting every [`Form`].
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Styles {
+    pub comments: Style,
+    pub functions: Style,
+    pub keywords: Style,
+    pub strings: Style,
+    pub variables: Style,
+    pub numbers: Style,
+    pub booleans: Style,
+    pub types: Style,
+    pub operators: Style,
+    pub conditionals: Style,
+    pub loops: Style,
+}
+
+/// Layers a [`Style`]'s modifiers onto a [`Form`]
+fn styled(form: Form, style: Style) -> Form {
+    let form = if style.has(Style::Italic) { form.italic() } else { form };
+    let form = if style.has(Style::Bold) { form.bold() } else { form };
+    if style.has(Style::Underline) {
+        form.underlined()
+    } else {
+        form
+    }
+}
+
+/// A single color from a Catppuccin palette
+///
+/// Stored as raw `u8` red/green/blue components instead of a bare
+/// hex string, so that plugin authors can inspect or recombine
+/// palette colors (e.g. in a [`Catppuccin::modify`] closure) without
+/// having to parse hex themselves.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Color {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl Color {
+    const fn from_hex(hex: &str) -> Self {
+        let bytes = hex.as_bytes();
+        Self {
+            r: hex_byte(bytes[1], bytes[2]),
+            g: hex_byte(bytes[3], bytes[4]),
+            b: hex_byte(bytes[5], bytes[6]),
+        }
+    }
+
+    /// Returns this color as a `"#rrggbb"` hex string
+    pub fn hex(&self) -> String {
+        format!("#{:02x}{:02x}{:02x}", self.r, self.g, self.b)
+    }
+
+    /// Returns this color's `(r, g, b)` components
+    pub fn rgb(&self) -> (u8, u8, u8) {
+        (self.r, self.g, self.b)
+    }
+
+    /// Linearly interpolates between this color and `other`
+    ///
+    /// `t` is clamped to `[0.0, 1.0]`, where `0.0` returns `self` and
+    /// `1.0` returns `other`. Each channel is blended independently,
+    /// `c = round(a + (b - a) * t)`.
+    pub fn mix(self, other: Color, t: f32) -> Color {
+        let t = t.clamp(0.0, 1.0);
+        let mix_channel = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * t).round() as u8;
+        Color {
+            r: mix_channel(self.r, other.r),
+            g: mix_channel(self.g, other.g),
+            b: mix_channel(self.b, other.b),
+        }
+    }
+
+    /// Mixes this color toward white by `amount` (clamped to `[0.0, 1.0]`)
+    pub fn lighten(self, amount: f32) -> Color {
+        self.mix(Color { r: 255, g: 255, b: 255 }, amount)
+    }
+
+    /// Mixes this color toward black by `amount` (clamped to `[0.0, 1.0]`)
+    pub fn darken(self, amount: f32) -> Color {
+        self.mix(Color { r: 0, g: 0, b: 0 }, amount)
+    }
+}
+
+impl From<Color> for (u8, u8, u8) {
+    fn from(color: Color) -> Self {
+        color.rgb()
+    }
+}
+
+const fn hex_digit(byte: u8) -> u8 {
+    match byte {
+        b'0'..=b'9' => byte - b'0',
+        b'a'..=b'f' => byte - b'a' + 10,
+        b'A'..=b'F' => byte - b'A' + 10,
+        _ => panic!("invalid hex digit in Catppuccin palette"),
+    }
+}
+
+const fn hex_byte(hi: u8, lo: u8) -> u8 {
+    hex_digit(hi) * 16 + hex_digit(lo)
+}
+
+#[derive(Clone, Copy)]
 pub struct Colors {
-    pub rosewater: &'static str,
-    pub flamingo: &'static str,
-    pub pink: &'static str,
-    pub mauve: &'static str,
-    pub red: &'static str,
-    pub maroon: &'static str,
-    pub peach: &'static str,
-    pub yellow: &'static str,
-    pub green: &'static str,
-    pub teal: &'static str,
-    pub sky: &'static str,
-    pub sapphire: &'static str,
-    pub blue: &'static str,
-    pub lavender: &'static str,
-    pub text: &'static str,
-    pub subtext1: &'static str,
-    pub subtext0: &'static str,
-    pub overlay2: &'static str,
-    pub overlay1: &'static str,
-    pub overlay0: &'static str,
-    pub surface2: &'static str,
-    pub surface1: &'static str,
-    pub surface0: &'static str,
-    pub base: &'static str,
-    pub mantle: &'static str,
-    pub crust: &'static str,
+    pub rosewater: Color,
+    pub flamingo: Color,
+    pub pink: Color,
+    pub mauve: Color,
+    pub red: Color,
+    pub maroon: Color,
+    pub peach: Color,
+    pub yellow: Color,
+    pub green: Color,
+    pub teal: Color,
+    pub sky: Color,
+    pub sapphire: Color,
+    pub blue: Color,
+    pub lavender: Color,
+    pub text: Color,
+    pub subtext1: Color,
+    pub subtext0: Color,
+    pub overlay2: Color,
+    pub overlay1: Color,
+    pub overlay0: Color,
+    pub surface2: Color,
+    pub surface1: Color,
+    pub surface0: Color,
+    pub base: Color,
+    pub mantle: Color,
+    pub crust: Color,
+}
+
+impl Colors {
+    /// Returns an iterator over all 26 named colors in this palette
+    pub fn iter(&self) -> impl Iterator<Item = (&'static str, Color)> {
+        [
+            ("rosewater", self.rosewater),
+            ("flamingo", self.flamingo),
+            ("pink", self.pink),
+            ("mauve", self.mauve),
+            ("red", self.red),
+            ("maroon", self.maroon),
+            ("peach", self.peach),
+            ("yellow", self.yellow),
+            ("green", self.green),
+            ("teal", self.teal),
+            ("sky", self.sky),
+            ("sapphire", self.sapphire),
+            ("blue", self.blue),
+            ("lavender", self.lavender),
+            ("text", self.text),
+            ("subtext1", self.subtext1),
+            ("subtext0", self.subtext0),
+            ("overlay2", self.overlay2),
+            ("overlay1", self.overlay1),
+            ("overlay0", self.overlay0),
+            ("surface2", self.surface2),
+            ("surface1", self.surface1),
+            ("surface0", self.surface0),
+            ("base", self.base),
+            ("mantle", self.mantle),
+            ("crust", self.crust),
+        ]
+        .into_iter()
+    }
 }
 
 const LATTE: Colors = Colors {
-    rosewater: "#dc8a78",
-    flamingo: "#dd7878",
-    pink: "#ea76cb",
-    mauve: "#8839ef",
-    red: "#d20f39",
-    maroon: "#e64553",
-    peach: "#fe640b",
-    yellow: "#df8e1d",
-    green: "#40a02b",
-    teal: "#179299",
-    sky: "#04a5e5",
-    sapphire: "#209fb5",
-    blue: "#1e66f5",
-    lavender: "#7287fd",
-    text: "#4c4f69",
-    subtext1: "#5c5f77",
-    subtext0: "#6c6f85",
-    overlay2: "#7c7f93",
-    overlay1: "#8c8fa1",
-    overlay0: "#9ca0b0",
-    surface2: "#acb0be",
-    surface1: "#bcc0cc",
-    surface0: "#ccd0da",
-    base: "#eff1f5",
-    mantle: "#e6e9ef",
-    crust: "#dce0e8",
+    rosewater: Color::from_hex("#dc8a78"),
+    flamingo: Color::from_hex("#dd7878"),
+    pink: Color::from_hex("#ea76cb"),
+    mauve: Color::from_hex("#8839ef"),
+    red: Color::from_hex("#d20f39"),
+    maroon: Color::from_hex("#e64553"),
+    peach: Color::from_hex("#fe640b"),
+    yellow: Color::from_hex("#df8e1d"),
+    green: Color::from_hex("#40a02b"),
+    teal: Color::from_hex("#179299"),
+    sky: Color::from_hex("#04a5e5"),
+    sapphire: Color::from_hex("#209fb5"),
+    blue: Color::from_hex("#1e66f5"),
+    lavender: Color::from_hex("#7287fd"),
+    text: Color::from_hex("#4c4f69"),
+    subtext1: Color::from_hex("#5c5f77"),
+    subtext0: Color::from_hex("#6c6f85"),
+    overlay2: Color::from_hex("#7c7f93"),
+    overlay1: Color::from_hex("#8c8fa1"),
+    overlay0: Color::from_hex("#9ca0b0"),
+    surface2: Color::from_hex("#acb0be"),
+    surface1: Color::from_hex("#bcc0cc"),
+    surface0: Color::from_hex("#ccd0da"),
+    base: Color::from_hex("#eff1f5"),
+    mantle: Color::from_hex("#e6e9ef"),
+    crust: Color::from_hex("#dce0e8"),
 };
 const FRAPPE: Colors = Colors {
-    rosewater: "#f2d5cf",
-    flamingo: "#eebebe",
-    pink: "#f4b8e4",
-    mauve: "#ca9ee6",
-    red: "#e78284",
-    maroon: "#ea999c",
-    peach: "#ef9f76",
-    yellow: "#e5c890",
-    green: "#a6d189",
-    teal: "#81c8be",
-    sky: "#99d1db",
-    sapphire: "#85c1dc",
-    blue: "#8caaee",
-    lavender: "#babbf1",
-    text: "#c6d0f5",
-    subtext1: "#b5bfe2",
-    subtext0: "#a5adce",
-    overlay2: "#949cbb",
-    overlay1: "#838ba7",
-    overlay0: "#737994",
-    surface2: "#626880",
-    surface1: "#51576d",
-    surface0: "#414559",
-    base: "#303446",
-    mantle: "#292c3c",
-    crust: "#232634",
+    rosewater: Color::from_hex("#f2d5cf"),
+    flamingo: Color::from_hex("#eebebe"),
+    pink: Color::from_hex("#f4b8e4"),
+    mauve: Color::from_hex("#ca9ee6"),
+    red: Color::from_hex("#e78284"),
+    maroon: Color::from_hex("#ea999c"),
+    peach: Color::from_hex("#ef9f76"),
+    yellow: Color::from_hex("#e5c890"),
+    green: Color::from_hex("#a6d189"),
+    teal: Color::from_hex("#81c8be"),
+    sky: Color::from_hex("#99d1db"),
+    sapphire: Color::from_hex("#85c1dc"),
+    blue: Color::from_hex("#8caaee"),
+    lavender: Color::from_hex("#babbf1"),
+    text: Color::from_hex("#c6d0f5"),
+    subtext1: Color::from_hex("#b5bfe2"),
+    subtext0: Color::from_hex("#a5adce"),
+    overlay2: Color::from_hex("#949cbb"),
+    overlay1: Color::from_hex("#838ba7"),
+    overlay0: Color::from_hex("#737994"),
+    surface2: Color::from_hex("#626880"),
+    surface1: Color::from_hex("#51576d"),
+    surface0: Color::from_hex("#414559"),
+    base: Color::from_hex("#303446"),
+    mantle: Color::from_hex("#292c3c"),
+    crust: Color::from_hex("#232634"),
 };
 
 const MACCHIATO: Colors = Colors {
-    rosewater: "#f4dbd6",
-    flamingo: "#f0c6c6",
-    pink: "#f5bde6",
-    mauve: "#c6a0f6",
-    red: "#ed8796",
-    maroon: "#ee99a0",
-    peach: "#f5a97f",
-    yellow: "#eed49f",
-    green: "#a6da95",
-    teal: "#8bd5ca",
-    sky: "#91d7e3",
-    sapphire: "#7dc4e4",
-    blue: "#8aadf4",
-    lavender: "#b7bdf8",
-    text: "#cad3f5",
-    subtext1: "#b8c0e0",
-    subtext0: "#a5adcb",
-    overlay2: "#939ab7",
-    overlay1: "#8087a2",
-    overlay0: "#6e738d",
-    surface2: "#5b6078",
-    surface1: "#494d64",
-    surface0: "#363a4f",
-    base: "#24273a",
-    mantle: "#1e2030",
-    crust: "#181926",
+    rosewater: Color::from_hex("#f4dbd6"),
+    flamingo: Color::from_hex("#f0c6c6"),
+    pink: Color::from_hex("#f5bde6"),
+    mauve: Color::from_hex("#c6a0f6"),
+    red: Color::from_hex("#ed8796"),
+    maroon: Color::from_hex("#ee99a0"),
+    peach: Color::from_hex("#f5a97f"),
+    yellow: Color::from_hex("#eed49f"),
+    green: Color::from_hex("#a6da95"),
+    teal: Color::from_hex("#8bd5ca"),
+    sky: Color::from_hex("#91d7e3"),
+    sapphire: Color::from_hex("#7dc4e4"),
+    blue: Color::from_hex("#8aadf4"),
+    lavender: Color::from_hex("#b7bdf8"),
+    text: Color::from_hex("#cad3f5"),
+    subtext1: Color::from_hex("#b8c0e0"),
+    subtext0: Color::from_hex("#a5adcb"),
+    overlay2: Color::from_hex("#939ab7"),
+    overlay1: Color::from_hex("#8087a2"),
+    overlay0: Color::from_hex("#6e738d"),
+    surface2: Color::from_hex("#5b6078"),
+    surface1: Color::from_hex("#494d64"),
+    surface0: Color::from_hex("#363a4f"),
+    base: Color::from_hex("#24273a"),
+    mantle: Color::from_hex("#1e2030"),
+    crust: Color::from_hex("#181926"),
 };
 
 const MOCHA: Colors = Colors {
-    rosewater: "#f5e0dc",
-    flamingo: "#f2cdcd",
-    pink: "#f5c2e7",
-    mauve: "#cba6f7",
-    red: "#f38ba8",
-    maroon: "#eba0ac",
-    peach: "#fab387",
-    yellow: "#f9e2af",
-    green: "#a6e3a1",
-    teal: "#94e2d5",
-    sky: "#89dceb",
-    sapphire: "#74c7ec",
-    blue: "#89b4fa",
-    lavender: "#b4befe",
-    text: "#cdd6f4",
-    subtext1: "#bac2de",
-    subtext0: "#a6adc8",
-    overlay2: "#9399b2",
-    overlay1: "#7f849c",
-    overlay0: "#6c7086",
-    surface2: "#585b70",
-    surface1: "#45475a",
-    surface0: "#313244",
-    base: "#1e1e2e",
-    mantle: "#181825",
-    crust: "#11111b",
+    rosewater: Color::from_hex("#f5e0dc"),
+    flamingo: Color::from_hex("#f2cdcd"),
+    pink: Color::from_hex("#f5c2e7"),
+    mauve: Color::from_hex("#cba6f7"),
+    red: Color::from_hex("#f38ba8"),
+    maroon: Color::from_hex("#eba0ac"),
+    peach: Color::from_hex("#fab387"),
+    yellow: Color::from_hex("#f9e2af"),
+    green: Color::from_hex("#a6e3a1"),
+    teal: Color::from_hex("#94e2d5"),
+    sky: Color::from_hex("#89dceb"),
+    sapphire: Color::from_hex("#74c7ec"),
+    blue: Color::from_hex("#89b4fa"),
+    lavender: Color::from_hex("#b4befe"),
+    text: Color::from_hex("#cdd6f4"),
+    subtext1: Color::from_hex("#bac2de"),
+    subtext0: Color::from_hex("#a6adc8"),
+    overlay2: Color::from_hex("#9399b2"),
+    overlay1: Color::from_hex("#7f849c"),
+    overlay0: Color::from_hex("#6c7086"),
+    surface2: Color::from_hex("#585b70"),
+    surface1: Color::from_hex("#45475a"),
+    surface0: Color::from_hex("#313244"),
+    base: Color::from_hex("#1e1e2e"),
+    mantle: Color::from_hex("#181825"),
+    crust: Color::from_hex("#11111b"),
 };